@@ -1,8 +1,60 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Copies the bundled, relocatable CPython distribution and the `gigui`
+/// project sources into `src-tauri/resources/` so Tauri ships them inside
+/// the app bundle and `python_runtime::init` can find them next to the
+/// resource dir at runtime, instead of relying on a developer's `.venv`.
+fn bundle_python_runtime() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let resources_dir = manifest_dir.join("resources");
+
+    let sources = [
+        ("python-dist/python-stdlib", "python-stdlib"),
+        ("python-dist/site-packages", "site-packages"),
+        ("../python", "python"),
+    ];
+
+    for (src, dest) in sources {
+        let src_path = manifest_dir.join(src);
+        if !src_path.is_dir() {
+            println!(
+                "cargo:warning=Skipping bundled Python resource '{}': {} not found",
+                dest,
+                src_path.display()
+            );
+            continue;
+        }
+        let dest_path = resources_dir.join(dest);
+        if let Err(e) = copy_dir_recursive(&src_path, &dest_path) {
+            println!(
+                "cargo:warning=Failed to bundle Python resource '{}': {}",
+                dest, e
+            );
+        }
+        println!("cargo:rerun-if-changed={}", src_path.display());
+    }
+}
+
+fn copy_dir_recursive(src: &Path, dest: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dest_path = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &dest_path)?;
+        } else {
+            fs::copy(entry.path(), &dest_path)?;
+        }
+    }
+    Ok(())
+}
+
 fn main() {
+    bundle_python_runtime();
+
     // Get Python configuration from the current Python executable
     let python_exe = env::var("PYO3_PYTHON")
         .or_else(|_| env::var("VIRTUAL_ENV").map(|venv| format!("{}/bin/python3", venv)))
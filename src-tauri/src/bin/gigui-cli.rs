@@ -0,0 +1,148 @@
+//! Headless entry point for CI/SSH use: parses settings from CLI flags or a
+//! JSON config file and drives the same `execute_analysis`/`get_blame_data`
+//! core that the Tauri commands use, so there is a single Python-calling
+//! code path regardless of front end. No windowing system required.
+//!
+//! Unlike the Tauri app's `setup()` hook, this binary never calls
+//! `python_runtime::init` — that step resolves the bundled, relocatable
+//! Python layout from a Tauri `AppHandle`'s resource directory, which this
+//! binary doesn't have. Instead every command it drives
+//! (`health_check`, `execute_analysis_core`, ...) goes through
+//! `commands::configure_python_path` before touching `gigui`, which sets up
+//! `sys.path` from the current directory, the user's `Settings` overrides and
+//! the compiled-in fallbacks, so no `AppHandle` is required. The exception is
+//! the `sub_interpreters` pool, when it is used: each sub-interpreter gets
+//! only the current directory's `python` and `src-tauri/src-python` dirs.
+
+use std::fs;
+use std::process::ExitCode;
+
+use gigui_lib::commands::{self, ProgressEvent, ProgressSink, Settings};
+
+/// Prints progress updates to stderr so stdout stays clean for the result
+/// when no `--output` file is given.
+struct StderrProgressSink;
+
+impl ProgressSink for StderrProgressSink {
+    fn send(&self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::Progress {
+                repo,
+                phase,
+                files_processed,
+                percent,
+            } => {
+                eprintln!("[{percent:>5.1}%] {repo}: {phase} ({files_processed} files)");
+            }
+            ProgressEvent::Aborted { repo } => {
+                eprintln!("aborted: {repo}");
+            }
+        }
+    }
+}
+
+struct CliArgs {
+    config: Option<String>,
+    inputs: Vec<String>,
+    output: Option<String>,
+    health_check: bool,
+    blame: bool,
+}
+
+fn parse_args() -> CliArgs {
+    let mut config = None;
+    let mut inputs = Vec::new();
+    let mut output = None;
+    let mut health_check = false;
+    let mut blame = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config = args.next(),
+            "--input" => {
+                if let Some(value) = args.next() {
+                    inputs.push(value);
+                }
+            }
+            "--output" => output = args.next(),
+            "--health-check" => health_check = true,
+            "--blame" => blame = true,
+            other => eprintln!("Ignoring unrecognized argument: {}", other),
+        }
+    }
+
+    CliArgs {
+        config,
+        inputs,
+        output,
+        health_check,
+        blame,
+    }
+}
+
+fn load_settings(args: &CliArgs) -> Result<Settings, String> {
+    let mut settings = match &args.config {
+        Some(path) => {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read config file '{}': {}", path, e))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse config file '{}': {}", path, e))?
+        }
+        None => Settings::default(),
+    };
+
+    if !args.inputs.is_empty() {
+        settings.input_fstrs = args.inputs.clone();
+    }
+
+    Ok(settings)
+}
+
+async fn run(args: CliArgs) -> Result<(), String> {
+    let settings = load_settings(&args)?;
+    // `load_settings` builds `Settings` straight from `--config`/defaults
+    // rather than through `get_settings`/`save_settings`, so nothing else
+    // populates the process-global python_override `configure_python_path`
+    // reads — without this, a configured python_binary/python_home/
+    // extra_sys_paths is silently ignored in headless use.
+    commands::update_python_override(&settings);
+
+    if args.health_check {
+        let report = commands::health_check().await?;
+        println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+
+        let engine_info = commands::get_engine_info().await?;
+        println!("{}", serde_json::to_string_pretty(&engine_info).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    let result_json = if args.blame {
+        let blame = commands::get_blame_data(settings).await?;
+        serde_json::to_string_pretty(&blame).map_err(|e| e.to_string())?
+    } else {
+        let result = commands::execute_analysis_core(settings, std::sync::Arc::new(StderrProgressSink)).await?;
+        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())?
+    };
+
+    match &args.output {
+        Some(path) => fs::write(path, result_json).map_err(|e| format!("Failed to write output file '{}': {}", path, e))?,
+        None => println!("{}", result_json),
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+    let args = parse_args();
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("gigui-cli: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
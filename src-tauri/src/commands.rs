@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 use pyo3::prelude::*;
+use pyo3::types::PyCFunction;
 use log::{debug, error};
+use std::path::PathBuf;
+use std::process::Command as StdCommand;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::ipc::Channel;
 
 // Keep existing Settings struct for type safety and compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +61,17 @@ pub struct Settings {
     pub gui_settings_full_path: bool,
     pub col_percent: i32,
 
+    // Python interpreter selection
+    // Empty strings / vecs mean "use the compiled-in PYO3_PYTHON/PYTHONHOME defaults".
+    pub python_binary: String,
+    pub python_home: String,
+    pub extra_sys_paths: Vec<String>,
+
+    // Environment verification
+    // When true, a failed startup environment check may create/populate
+    // .venv and re-verify; otherwise a failed check only disables analysis.
+    pub allow_bootstrap: bool,
+
     // Additional required fields for Python backend compatibility
     pub ex_author_patterns: Vec<String>,
     pub ex_email_patterns: Vec<String>,
@@ -155,6 +172,14 @@ impl Default for Settings {
             gui_settings_full_path: false,
             col_percent: 75,
 
+            // Python interpreter selection
+            python_binary: String::new(),
+            python_home: String::new(),
+            extra_sys_paths: vec![],
+
+            // Environment verification
+            allow_bootstrap: false,
+
             // Additional required fields for Python backend compatibility
             ex_author_patterns: vec![],
             ex_email_patterns: vec![],
@@ -249,6 +274,223 @@ pub struct BlameEntry {
     pub content: String,
 }
 
+/// A single progress update streamed to the frontend over the
+/// `execute_analysis` `Channel` while an analysis is running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum ProgressEvent {
+    Progress {
+        repo: String,
+        phase: String,
+        files_processed: i32,
+        percent: f64,
+    },
+    Aborted {
+        repo: String,
+    },
+}
+
+/// Set by `cancel_analysis` and polled from inside the GIL loop (and between
+/// per-repo chunks driven by `git_log_chunk_size`/`blame_chunk_size`) so a
+/// running analysis can be aborted cleanly instead of left to run to
+/// completion or hang the UI.
+pub(crate) fn cancel_requested() -> &'static AtomicBool {
+    static FLAG: OnceLock<AtomicBool> = OnceLock::new();
+    FLAG.get_or_init(|| AtomicBool::new(false))
+}
+
+/// Destination for `ProgressEvent`s emitted during `execute_analysis`.
+/// Abstracts over the Tauri IPC `Channel` the GUI uses and the plain
+/// stdio sink `gigui-cli` uses, so the Python-calling core stays a single
+/// code path shared by both front ends.
+pub trait ProgressSink: Send + Sync {
+    fn send(&self, event: ProgressEvent);
+}
+
+impl ProgressSink for Channel<ProgressEvent> {
+    fn send(&self, event: ProgressEvent) {
+        let _ = Channel::send(self, event);
+    }
+}
+
+// User-configurable interpreter override, populated from `Settings.python_binary`
+// / `python_home` / `extra_sys_paths` whenever settings are saved. Falls back to
+// the compiled-in PYO3_PYTHON/PYTHONHOME env values (baked into the binary by
+// build.rs) when left empty.
+#[derive(Debug, Clone, Default)]
+struct PythonOverride {
+    python_binary: Option<String>,
+    python_home: Option<String>,
+    extra_sys_paths: Vec<String>,
+}
+
+fn python_override() -> &'static Mutex<PythonOverride> {
+    static OVERRIDE: OnceLock<Mutex<PythonOverride>> = OnceLock::new();
+    OVERRIDE.get_or_init(|| Mutex::new(PythonOverride::default()))
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+/// Remembers the user's interpreter choice so later `call_python_function`
+/// calls (including ones that take no arguments, like `get_settings`) can
+/// honor it without needing `Settings` threaded through every call site.
+/// `pub` (rather than `pub(crate)`) because `gigui-cli` — a separate binary
+/// crate — builds `Settings` straight from a config file instead of going
+/// through `get_settings`/`save_settings`, so it has to call this itself.
+pub fn update_python_override(settings: &Settings) {
+    let mut over = python_override().lock().unwrap();
+    over.python_binary = non_empty(&settings.python_binary);
+    over.python_home = non_empty(&settings.python_home);
+    over.extra_sys_paths = settings.extra_sys_paths.clone();
+
+    // The configured binary may have changed; drop any cached compat result
+    // so the next `configure_python_path` call re-derives it instead of
+    // reusing a stale verdict for a different interpreter.
+    *compat_cache().lock().unwrap() = None;
+}
+
+/// Caches the outcome of `check_interpreter_compat` for the currently
+/// configured `python_binary`: that check forks a subprocess purely to
+/// re-derive a value (the configured interpreter's major.minor) that cannot
+/// change without a settings update, so doing it on every `configure_python_path`
+/// call (i.e. on every Python call — `execute_analysis`, `get_settings`,
+/// `save_settings`, ...) would fork a process on a hot path for nothing.
+/// Invalidated by `update_python_override` whenever the binary changes.
+struct CompatCache {
+    python_binary: String,
+    result: Result<(), String>,
+}
+
+fn compat_cache() -> &'static Mutex<Option<CompatCache>> {
+    static CACHE: OnceLock<Mutex<Option<CompatCache>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn check_interpreter_compat_cached(py: Python, python_binary: &str) -> PyResult<()> {
+    let mut cache = compat_cache().lock().unwrap();
+    if let Some(cached) = cache.as_ref() {
+        if cached.python_binary == python_binary {
+            return cached
+                .result
+                .clone()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e));
+        }
+    }
+
+    let result = check_interpreter_compat(py, python_binary).map_err(|e| e.to_string());
+    *cache = Some(CompatCache {
+        python_binary: python_binary.to_string(),
+        result: result.clone(),
+    });
+    result.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e))
+}
+
+/// PyO3 links exactly one CPython interpreter into this process, so a
+/// `python_binary` setting cannot swap in a different interpreter at runtime;
+/// the interpreter settings can only add `sys.path` entries for that same
+/// interpreter. Detect a version mismatch up front and surface it
+/// as a clear `Result::Err` instead of letting the mismatch fail silently
+/// deep inside a GIL call.
+fn check_interpreter_compat(py: Python, python_binary: &str) -> PyResult<()> {
+    let output = StdCommand::new(python_binary)
+        .args(["-c", "import sys; print(f'{sys.version_info[0]}.{sys.version_info[1]}')"])
+        .output()
+        .map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Configured python_binary '{}' could not be run: {}",
+                python_binary, e
+            ))
+        })?;
+    let configured_version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let sys = py.import_bound("sys")?;
+    let version_info = sys.getattr("version_info")?;
+    let major: u32 = version_info.get_item(0)?.extract()?;
+    let minor: u32 = version_info.get_item(1)?.extract()?;
+    let embedded_version = format!("{}.{}", major, minor);
+
+    if configured_version != embedded_version {
+        return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+            "Configured python_binary '{}' is Python {}, but this build embeds a single \
+             Python {} interpreter via PyO3; they must match. Rebuild against '{}' or pick \
+             a {} interpreter instead.",
+            python_binary, configured_version, embedded_version, python_binary, embedded_version
+        )));
+    }
+
+    Ok(())
+}
+
+/// Real CPython layout is `lib/pythonX.Y/site-packages`, not `lib/site-packages`.
+/// Pulled out of `configure_python_path` so this path arithmetic is testable
+/// without a `Python` GIL token.
+fn site_packages_dir(python_home: &str, major: u32, minor: u32) -> PathBuf {
+    PathBuf::from(python_home)
+        .join("lib")
+        .join(format!("python{}.{}", major, minor))
+        .join("site-packages")
+}
+
+/// Inserts the project's `python` dir plus any user overrides
+/// (`python_home`-derived `site-packages`, `extra_sys_paths`) onto `sys.path`,
+/// honoring the configured interpreter and checking it's compatible with the
+/// embedded one before anything is imported. Shared by every code path that
+/// imports `gigui` in the main interpreter through these helpers — the
+/// Python-calling helpers below, `environment::check`, and (transitively, via
+/// those) `gigui-cli` — so none of them imports it before `sys.path` is set
+/// up. `python_runtime::init` and the `sub_interpreters` pool insert their own
+/// `sys.path` entries instead and do not apply the overrides.
+pub(crate) fn configure_python_path(py: Python) -> PyResult<()> {
+    let over = python_override().lock().unwrap().clone();
+
+    if let Some(python_binary) = &over.python_binary {
+        check_interpreter_compat_cached(py, python_binary)?;
+    }
+
+    let sys = py.import_bound("sys")?;
+    let path = sys.getattr("path")?;
+
+    // Get the current working directory and add python subdirectory
+    let current_dir = std::env::current_dir()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to get current directory: {}", e)))?;
+    let python_dir = current_dir.join("python");
+
+    debug!("Adding Python path: {}", python_dir.display());
+    path.call_method1("insert", (0, python_dir.to_string_lossy().as_ref()))?;
+
+    // Import the main module from src-tauri/src-python/
+    let src_python_dir = current_dir.join("src-tauri").join("src-python");
+    debug!("Adding src-python path: {}", src_python_dir.display());
+    path.call_method1("insert", (0, src_python_dir.to_string_lossy().as_ref()))?;
+
+    // An unset python_home falls back to the PYTHONHOME baked in by build.rs.
+    let python_home = over
+        .python_home
+        .clone()
+        .or_else(|| option_env!("PYTHONHOME").map(str::to_string));
+    if let Some(python_home) = &python_home {
+        let version_info = sys.getattr("version_info")?;
+        let major: u32 = version_info.get_item(0)?.extract()?;
+        let minor: u32 = version_info.get_item(1)?.extract()?;
+        let site_packages = site_packages_dir(python_home, major, minor);
+        debug!("Adding python_home site-packages: {}", site_packages.display());
+        path.call_method1("insert", (0, site_packages.to_string_lossy().as_ref()))?;
+    }
+
+    for extra in &over.extra_sys_paths {
+        debug!("Adding extra_sys_paths entry: {}", extra);
+        path.call_method1("insert", (0, extra.as_str()))?;
+    }
+
+    Ok(())
+}
+
 // Claude's elegant helper function for calling Python functions
 async fn call_python_function<T, R>(
     function_name: &str,
@@ -261,22 +503,7 @@ where
     debug!("Calling Python function: {}", function_name);
 
     Python::with_gil(|py| -> PyResult<R> {
-        // Add the project's Python directory to the path
-        let sys = py.import_bound("sys")?;
-        let path = sys.getattr("path")?;
-
-        // Get the current working directory and add python subdirectory
-        let current_dir = std::env::current_dir()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to get current directory: {}", e)))?;
-        let python_dir = current_dir.join("python");
-
-        debug!("Adding Python path: {}", python_dir.display());
-        path.call_method1("insert", (0, python_dir.to_string_lossy().as_ref()))?;
-
-        // Import the main module from src-tauri/src-python/
-        let src_python_dir = current_dir.join("src-tauri").join("src-python");
-        debug!("Adding src-python path: {}", src_python_dir.display());
-        path.call_method1("insert", (0, src_python_dir.to_string_lossy().as_ref()))?;
+        configure_python_path(py)?;
 
         debug!("Importing Python main module");
         let main_module = py.import_bound("main")?;
@@ -312,22 +539,7 @@ where
     debug!("Calling Python function (no args): {}", function_name);
 
     Python::with_gil(|py| -> PyResult<R> {
-        // Add the project's Python directory to the path
-        let sys = py.import_bound("sys")?;
-        let path = sys.getattr("path")?;
-
-        // Get the current working directory and add python subdirectory
-        let current_dir = std::env::current_dir()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to get current directory: {}", e)))?;
-        let python_dir = current_dir.join("python");
-
-        debug!("Adding Python path: {}", python_dir.display());
-        path.call_method1("insert", (0, python_dir.to_string_lossy().as_ref()))?;
-
-        // Import the main module from src-tauri/src-python/
-        let src_python_dir = current_dir.join("src-tauri").join("src-python");
-        debug!("Adding src-python path: {}", src_python_dir.display());
-        path.call_method1("insert", (0, src_python_dir.to_string_lossy().as_ref()))?;
+        configure_python_path(py)?;
 
         debug!("Importing Python main module");
         let main_module = py.import_bound("main")?;
@@ -349,19 +561,171 @@ where
     })
 }
 
+/// Like `call_python_function`, but also hands the Python side a progress
+/// callback (so `gigui` can stream `analysis://progress`-style updates back
+/// through `on_progress`) and a cancellation check (backed by
+/// `cancel_requested`) it can poll between chunks.
+async fn call_python_function_with_progress<T, R>(
+    function_name: &str,
+    args: T,
+    on_progress: Arc<dyn ProgressSink>,
+) -> Result<R, String>
+where
+    T: Serialize,
+    R: for<'de> Deserialize<'de>,
+{
+    debug!("Calling Python function (with progress): {}", function_name);
+    cancel_requested().store(false, Ordering::SeqCst);
+
+    Python::with_gil(|py| -> PyResult<R> {
+        configure_python_path(py)?;
+
+        debug!("Importing Python main module");
+        let main_module = py.import_bound("main")?;
+
+        let args_json = serde_json::to_string(&args)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to serialize args: {}", e)))?;
+
+        let progress_callback = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |call_args, _kwargs| -> PyResult<()> {
+                let repo: String = call_args.get_item(0)?.extract()?;
+                let phase: String = call_args.get_item(1)?.extract()?;
+                let files_processed: i32 = call_args.get_item(2)?.extract()?;
+                let percent: f64 = call_args.get_item(3)?.extract()?;
+                on_progress.send(ProgressEvent::Progress {
+                    repo,
+                    phase,
+                    files_processed,
+                    percent,
+                });
+                Ok(())
+            },
+        )?;
+
+        let is_cancelled = PyCFunction::new_closure_bound(py, None, None, |_args, _kwargs| -> PyResult<bool> {
+            Ok(cancel_requested().load(Ordering::SeqCst))
+        })?;
+
+        debug!("Executing Python function: {}", function_name);
+        let result = main_module.call_method1(function_name, (args_json, progress_callback, is_cancelled))?;
+        let result_str: String = result.extract()?;
+
+        debug!("Python function {} completed, result length: {} bytes", function_name, result_str.len());
+
+        serde_json::from_str(&result_str)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Failed to deserialize result: {}", e)))
+    })
+    .map_err(|e| {
+        error!("Python call to {} failed: {}", function_name, e);
+        format!("Python call failed: {}", e)
+    })
+}
+
+/// The Python-calling core behind `execute_analysis`, shared by the Tauri
+/// command and `gigui-cli` so there is exactly one code path that picks the
+/// sub-interpreter pool vs. single-interpreter path and drives progress
+/// reporting. `on_progress` is an `Arc` (rather than a bare generic sink) so
+/// this function can keep a handle to it after handing a clone to the
+/// Python-calling helpers, and use it to report cancellation once the call
+/// returns.
+pub async fn execute_analysis_core(settings: Settings, on_progress: Arc<dyn ProgressSink>) -> Result<AnalysisResult, String> {
+    ensure_environment_verified(&settings).await?;
+
+    #[cfg(feature = "sub_interpreters")]
+    if crate::sub_interpreter_pool::should_use_pool(&settings, &settings.input_fstrs) {
+        return execute_analysis_via_pool(settings, on_progress).await;
+    }
+
+    let result: AnalysisResult =
+        call_python_function_with_progress("execute_analysis", settings, Arc::clone(&on_progress)).await?;
+
+    if cancel_requested().load(Ordering::SeqCst) {
+        on_progress.send(ProgressEvent::Aborted { repo: String::new() });
+        // Match `execute_analysis_via_pool`'s contract: cancellation always
+        // yields `success: false` with this exact error message, regardless
+        // of which execution path ran, so the frontend sees one shape for
+        // "aborted" instead of two. Whatever repositories the Python side
+        // already finished are kept, since the result is explicitly partial.
+        return Ok(AnalysisResult {
+            repositories: result.repositories,
+            success: false,
+            error: Some("Analysis cancelled".to_string()),
+        });
+    }
+
+    Ok(result)
+}
+
 // Clean Tauri commands using the helper functions
 #[tauri::command]
-pub async fn execute_analysis(settings: Settings) -> Result<AnalysisResult, String> {
-    call_python_function("execute_analysis", settings).await
+pub async fn execute_analysis(
+    settings: Settings,
+    on_progress: Channel<ProgressEvent>,
+) -> Result<AnalysisResult, String> {
+    execute_analysis_core(settings, Arc::new(on_progress)).await
+}
+
+/// Requests that the in-flight `execute_analysis` call abort at its next
+/// cancellation check. A no-op if no analysis is running.
+#[tauri::command]
+pub fn cancel_analysis() {
+    debug!("Cancellation requested for in-flight analysis");
+    cancel_requested().store(true, Ordering::SeqCst);
+}
+
+/// Parallel path for `execute_analysis`: runs one repo per isolated
+/// sub-interpreter (see `sub_interpreter_pool`), forwarding the same
+/// progress sink and `cancel_requested` flag the single-interpreter path
+/// uses, and merges their JSON-serialized `RepositoryResult`s into a single
+/// `AnalysisResult`.
+#[cfg(feature = "sub_interpreters")]
+async fn execute_analysis_via_pool(settings: Settings, on_progress: Arc<dyn ProgressSink>) -> Result<AnalysisResult, String> {
+    cancel_requested().store(false, Ordering::SeqCst);
+
+    let repo_fstrs = settings.input_fstrs.clone();
+    let pool_progress = Arc::clone(&on_progress);
+    let (repo_jsons, aborted) = tokio::task::spawn_blocking(move || {
+        crate::sub_interpreter_pool::analyze_repos_in_parallel(&settings, &repo_fstrs, pool_progress)
+    })
+    .await
+    .map_err(|e| format!("Sub-interpreter pool task panicked: {}", e))??;
+
+    let repositories = repo_jsons
+        .into_iter()
+        .map(|json| {
+            serde_json::from_str::<RepositoryResult>(&json)
+                .map_err(|e| format!("Failed to deserialize repository result: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // A cancel that arrives while the last chunk runs is only seen by the
+    // Python side, so the flag is checked again once every worker returned.
+    let aborted = aborted || cancel_requested().load(Ordering::SeqCst);
+
+    if aborted {
+        on_progress.send(ProgressEvent::Aborted { repo: String::new() });
+    }
+
+    Ok(AnalysisResult {
+        repositories,
+        success: !aborted,
+        error: if aborted { Some("Analysis cancelled".to_string()) } else { None },
+    })
 }
 
 #[tauri::command]
 pub async fn get_settings() -> Result<Settings, String> {
-    call_python_function_no_args("get_settings").await
+    let settings: Settings = call_python_function_no_args("get_settings").await?;
+    update_python_override(&settings);
+    Ok(settings)
 }
 
 #[tauri::command]
 pub async fn save_settings(settings: Settings) -> Result<(), String> {
+    update_python_override(&settings);
     call_python_function("save_settings", settings).await
 }
 
@@ -375,12 +739,86 @@ pub async fn get_performance_stats() -> Result<serde_json::Value, String> {
     call_python_function_no_args("get_performance_stats").await
 }
 
+/// Refuses to run analysis commands until `crate::environment` has verified
+/// the embedded interpreter can import `gigui`. If the environment isn't
+/// verified yet and `settings.allow_bootstrap` is set, tries once to repair
+/// it (creating/populating `.venv`) before giving up.
+async fn ensure_environment_verified(settings: &Settings) -> Result<(), String> {
+    if crate::environment::is_verified() {
+        return Ok(());
+    }
+
+    let allow_bootstrap = settings.allow_bootstrap;
+    let python_binary = non_empty(&settings.python_binary).unwrap_or_else(|| "python3".to_string());
+
+    let report = Python::with_gil(|py| crate::environment::check(py))
+        .map_err(|e| format!("Environment check failed: {}", e))?;
+
+    let report = if report.healthy || !allow_bootstrap {
+        if !report.healthy {
+            log::warn!("Environment is unhealthy and allow_bootstrap is disabled; analysis commands remain disabled");
+        }
+        report
+    } else {
+        // `bootstrap` shells out to `python -m venv` / `pip install`, which can
+        // take minutes: run it on a blocking-pool thread, with no GIL held,
+        // so it doesn't stall the async runtime or every other Python call.
+        let site_packages = tokio::task::spawn_blocking(move || crate::environment::bootstrap(&python_binary))
+            .await
+            .map_err(|e| format!("Bootstrap task panicked: {}", e))??;
+
+        Python::with_gil(|py| crate::environment::recheck_with_site_packages(py, &site_packages))
+            .map_err(|e| format!("Environment re-check after bootstrap failed: {}", e))?
+    };
+
+    if !report.healthy {
+        return Err(format!(
+            "Python environment is not verified; analysis commands are disabled. Run health_check for details. Report: {}",
+            serde_json::to_string(&report).unwrap_or_default()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Structured startup-verification report (see `crate::environment`) plus,
+/// best-effort, whatever the Python side's own `health_check` returns.
 #[tauri::command]
 pub async fn health_check() -> Result<serde_json::Value, String> {
-    call_python_function_no_args("health_check").await
+    let environment = Python::with_gil(|py| crate::environment::check(py))
+        .map_err(|e| format!("Environment check failed: {}", e))?;
+
+    let python_backend = call_python_function_no_args::<serde_json::Value>("health_check")
+        .await
+        .unwrap_or_else(|e| serde_json::json!({ "error": e }));
+
+    Ok(serde_json::json!({
+        "environment": environment,
+        "python_backend": python_backend,
+    }))
 }
 
 #[tauri::command]
 pub async fn get_blame_data(settings: Settings) -> Result<serde_json::Value, String> {
+    ensure_environment_verified(&settings).await?;
     call_python_function("get_blame_data", settings).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_empty_treats_blank_string_as_absent() {
+        assert_eq!(non_empty(""), None);
+        assert_eq!(non_empty("python3.11"), Some("python3.11".to_string()));
+    }
+
+    #[test]
+    fn site_packages_dir_includes_versioned_subdir() {
+        assert_eq!(
+            site_packages_dir("/opt/py", 3, 11),
+            PathBuf::from("/opt/py/lib/python3.11/site-packages")
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Startup environment verification and self-repair.
+//!
+//! Promotes the ad-hoc PyO3 import test (`debug_pyo3/test_gigui_import.rs`,
+//! which just checked `import gigui`, `gigui.api`, `gigui.core.orchestrator`,
+//! `gigui.analysis.blame.engine` and printed to the log) into a structured
+//! report: for each required module, whether it imported, its version, and
+//! the `sys.path` entry it was resolved from, plus the interpreter
+//! version/ABI and whether it matches what `build.rs` linked against. The
+//! app refuses to run analysis commands unless the most recent run of this
+//! check passed; see `is_verified`/`mark_verified`.
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use log::info;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+const REQUIRED_MODULES: &[&str] = &[
+    "gigui",
+    "gigui.api",
+    "gigui.core.orchestrator",
+    "gigui.analysis.blame.engine",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleCheck {
+    pub module: String,
+    pub imported: bool,
+    pub version: Option<String>,
+    pub resolved_from: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentReport {
+    pub interpreter_version: String,
+    pub interpreter_abi: String,
+    pub linked_python: Option<String>,
+    pub linked_matches_interpreter: bool,
+    pub modules: Vec<ModuleCheck>,
+    pub healthy: bool,
+}
+
+fn verified_flag() -> &'static AtomicBool {
+    static FLAG: OnceLock<AtomicBool> = OnceLock::new();
+    FLAG.get_or_init(|| AtomicBool::new(false))
+}
+
+pub fn is_verified() -> bool {
+    verified_flag().load(Ordering::SeqCst)
+}
+
+fn mark_verified(verified: bool) {
+    verified_flag().store(verified, Ordering::SeqCst);
+}
+
+fn check_module(py: Python, module: &str) -> ModuleCheck {
+    match py.import_bound(module) {
+        Ok(m) => {
+            let version = m
+                .getattr("__version__")
+                .ok()
+                .and_then(|v| v.extract::<String>().ok());
+            let resolved_from = m
+                .getattr("__file__")
+                .ok()
+                .and_then(|v| v.extract::<String>().ok());
+            ModuleCheck {
+                module: module.to_string(),
+                imported: true,
+                version,
+                resolved_from,
+                error: None,
+            }
+        }
+        Err(e) => ModuleCheck {
+            module: module.to_string(),
+            imported: false,
+            version: None,
+            resolved_from: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Runs the structured import check against the embedded interpreter and
+/// records whether the app is allowed to run analysis commands.
+///
+/// Must configure `sys.path` the same way every other Python-calling code
+/// path does before importing anything — otherwise this check (and anything
+/// gated on `is_verified`) fails in the bundled/headless layouts that don't
+/// happen to already have `gigui` importable from the interpreter's default
+/// path.
+pub fn check(py: Python) -> PyResult<EnvironmentReport> {
+    crate::commands::configure_python_path(py)?;
+
+    let sys = py.import_bound("sys")?;
+    let version_info = sys.getattr("version_info")?;
+    let major: u32 = version_info.get_item(0)?.extract()?;
+    let minor: u32 = version_info.get_item(1)?.extract()?;
+    let patch: u32 = version_info.get_item(2)?.extract()?;
+    let interpreter_version = format!("{}.{}.{}", major, minor, patch);
+    let interpreter_abi: String = sys.getattr("abiflags")?.extract()?;
+
+    // `build.rs` records the interpreter it linked PyO3 against via
+    // cargo:rustc-env=PYO3_PYTHON; option_env! because that step is best-effort.
+    let linked_python = option_env!("PYO3_PYTHON").map(|s| s.to_string());
+    let linked_matches_interpreter = linked_python
+        .as_deref()
+        .map(|linked| linked_version_matches(linked, &interpreter_version))
+        .unwrap_or(true);
+
+    let modules: Vec<ModuleCheck> = REQUIRED_MODULES
+        .iter()
+        .map(|module| check_module(py, module))
+        .collect();
+
+    let healthy = linked_matches_interpreter && modules.iter().all(|m| m.imported);
+
+    let report = EnvironmentReport {
+        interpreter_version,
+        interpreter_abi,
+        linked_python,
+        linked_matches_interpreter,
+        modules,
+        healthy,
+    };
+
+    mark_verified(healthy);
+    Ok(report)
+}
+
+/// Best-effort: the recorded `PYO3_PYTHON` path usually ends with
+/// `pythonX.Y` or `python3`; treat it as matching unless we can positively
+/// show a different major.minor.
+///
+/// Uses `rfind`, not `find`: a path with a `pythonX.Y`-shaped *directory*
+/// component ahead of the actual binary name (e.g.
+/// `/opt/python3.x/bin/python3.13`) would otherwise match that directory
+/// first, truncate at the non-digit `x`, and spuriously report a mismatch
+/// even though the trailing binary name matches fine.
+fn linked_version_matches(linked_python: &str, interpreter_version: &str) -> bool {
+    let interpreter_major_minor = interpreter_version
+        .rsplit_once('.')
+        .map(|(head, _)| head)
+        .unwrap_or(interpreter_version);
+    if let Some(idx) = linked_python.rfind("python3.") {
+        let tail = &linked_python[idx + "python".len()..];
+        let digits: String = tail.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+        return digits == interpreter_major_minor || digits.is_empty();
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_version_matches_same_major_minor() {
+        assert!(linked_version_matches("/usr/bin/python3.11", "3.11.4"));
+    }
+
+    #[test]
+    fn linked_version_matches_rejects_different_major_minor() {
+        assert!(!linked_version_matches("/usr/bin/python3.10", "3.11.4"));
+    }
+
+    #[test]
+    fn linked_version_matches_ignores_unversioned_binary_name() {
+        assert!(linked_version_matches("/usr/bin/python3", "3.11.4"));
+    }
+
+    #[test]
+    fn linked_version_matches_uses_the_binary_not_a_versioned_parent_dir() {
+        // The `python3.x` directory component must not be mistaken for the
+        // actual interpreter; the trailing `python3.13` is what matters.
+        assert!(linked_version_matches("/opt/python3.x/bin/python3.13", "3.13.1"));
+    }
+}
+
+/// Creates (if needed) the project `.venv` and `pip install`s the `gigui`
+/// requirements into it, returning the venv's `site-packages` directory so
+/// the caller can make it visible to the embedded interpreter. Only runs
+/// when explicitly allowed by the `allow_bootstrap` setting — this mutates
+/// the user's filesystem and should never happen silently.
+///
+/// Installs non-editable (no `-e`) on purpose: an editable install only
+/// places an importable `__editable__*.pth`/finder, which `site.py`
+/// executes at interpreter *startup* — inserting the venv's `purelib` onto
+/// `sys.path` at runtime after the fact never runs it, so `import gigui`
+/// would still fail post-bootstrap. A regular install puts `gigui` itself
+/// under `purelib`, which a plain `sys.path` insert can see immediately.
+///
+/// Purely blocking subprocess work and deliberately free of any `Python`/GIL
+/// token: PyO3 embeds exactly one interpreter per process, so installing
+/// into a *new* venv can never change what that interpreter already has
+/// loaded — the caller still has to point `sys.path` at the directory this
+/// returns and re-check. Callers should run this via `spawn_blocking` (it
+/// can take minutes) rather than awaiting it directly on the async runtime.
+pub fn bootstrap(python_binary: &str) -> Result<std::path::PathBuf, String> {
+    let venv_dir = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?
+        .join(".venv");
+
+    if !venv_dir.is_dir() {
+        info!("Bootstrapping missing .venv at {}", venv_dir.display());
+        let status = Command::new(python_binary)
+            .args(["-m", "venv"])
+            .arg(&venv_dir)
+            .status()
+            .map_err(|e| format!("Failed to run '{} -m venv': {}", python_binary, e))?;
+        if !status.success() {
+            return Err(format!("'{} -m venv {}' exited with {}", python_binary, venv_dir.display(), status));
+        }
+    }
+
+    let venv_python = venv_dir.join("bin").join("python3");
+    info!("Installing gigui requirements into {}", venv_dir.display());
+    let status = Command::new(&venv_python)
+        .args(["-m", "pip", "install", "./python"])
+        .status()
+        .map_err(|e| format!("Failed to run pip install: {}", e))?;
+    if !status.success() {
+        return Err(format!("pip install into {} exited with {}", venv_dir.display(), status));
+    }
+
+    let output = Command::new(&venv_python)
+        .args(["-c", "import sysconfig; print(sysconfig.get_path('purelib'))"])
+        .output()
+        .map_err(|e| format!("Failed to resolve site-packages for {}: {}", venv_dir.display(), e))?;
+    if !output.status.success() {
+        return Err(format!("Resolving site-packages for {} exited with {}", venv_dir.display(), output.status));
+    }
+
+    Ok(std::path::PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+/// Points the embedded interpreter's `sys.path` at a bootstrapped venv's
+/// `site-packages` and re-runs `check`. Split out from `bootstrap` itself so
+/// the GIL is only held for this quick, non-blocking step — not for the
+/// `pip install` that produced `site_packages`.
+pub fn recheck_with_site_packages(py: Python, site_packages: &std::path::Path) -> PyResult<EnvironmentReport> {
+    let sys = py.import_bound("sys")?;
+    let path = sys.getattr("path")?;
+    path.call_method1("insert", (0, site_packages.to_string_lossy().as_ref()))?;
+    check(py)
+}
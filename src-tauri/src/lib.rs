@@ -0,0 +1,65 @@
+pub mod commands;
+pub mod environment;
+pub mod python_runtime;
+#[cfg(feature = "sub_interpreters")]
+pub mod sub_interpreter_pool;
+
+use pyo3::prelude::*;
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .setup(|app| {
+            // `python_runtime::init` only succeeds when the relocatable,
+            // bundled Python distribution (python-stdlib/site-packages/python
+            // under the resource dir) was actually copied in by `build.rs` —
+            // e.g. any `cargo tauri dev` run where `python-dist/` was absent
+            // at build time. That's a dev-environment gap, not a reason to
+            // crash the whole app before settings (and a possible
+            // `python_binary` override) are even loaded: log it and fall
+            // through to `environment::check`, which configures `sys.path`
+            // from the `current_dir`-based layout `configure_python_path`
+            // already uses everywhere else.
+            if let Err(e) = python_runtime::init(app.handle()) {
+                log::warn!(
+                    "Bundled Python runtime unavailable ({}); falling back to the current_dir-based layout",
+                    e
+                );
+            }
+
+            // Best-effort initial verification so analysis commands are
+            // already enabled by the time the frontend's first request
+            // arrives; `allow_bootstrap` isn't known yet this early (it
+            // lives in persisted Settings), so this pass never bootstraps.
+            let report = Python::with_gil(|py| environment::check(py))?;
+            if !report.healthy {
+                log::warn!("Startup environment check failed; analysis commands remain disabled until health_check passes");
+            }
+
+            Ok(())
+        })
+        .plugin(tauri_plugin_python::init_and_register(vec![
+            "execute_analysis",
+            "cancel_analysis",
+            "get_settings",
+            "save_settings",
+            "get_engine_info",
+            "get_performance_stats",
+            "health_check"
+        ]))
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_os::init())
+        .invoke_handler(tauri::generate_handler![
+            commands::execute_analysis,
+            commands::cancel_analysis,
+            commands::get_settings,
+            commands::save_settings,
+            commands::get_engine_info,
+            commands::get_performance_stats,
+            commands::health_check
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
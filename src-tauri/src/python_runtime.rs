@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use log::{debug, info};
+use pyo3::prelude::*;
+use tauri::{AppHandle, Manager};
+
+/// Paths to the pieces of the bundled, relocatable Python distribution,
+/// resolved relative to the app's resource directory.
+struct BundledLayout {
+    python_home: PathBuf,
+    stdlib: PathBuf,
+    site_packages: PathBuf,
+    project_python: PathBuf,
+}
+
+impl BundledLayout {
+    /// Locates `python-stdlib`, `site-packages` and the project `python`
+    /// directory under the Tauri resource dir, instead of assuming a
+    /// developer-machine `.venv` layout next to `std::env::current_dir()`.
+    fn resolve(app: &AppHandle) -> Result<Self, String> {
+        let resource_dir = app
+            .path()
+            .resource_dir()
+            .map_err(|e| format!("Failed to resolve resource directory: {}", e))?;
+
+        let python_home = resource_dir.join("python-stdlib");
+        let stdlib = python_home.clone();
+        let site_packages = resource_dir.join("site-packages");
+        let project_python = resource_dir.join("python");
+
+        for (label, dir) in [
+            ("python-stdlib", &python_home),
+            ("site-packages", &site_packages),
+            ("python", &project_python),
+        ] {
+            if !dir.is_dir() {
+                return Err(format!(
+                    "Bundled Python resource '{}' is missing at {}",
+                    label,
+                    dir.display()
+                ));
+            }
+        }
+
+        Ok(BundledLayout {
+            python_home,
+            stdlib,
+            site_packages,
+            project_python,
+        })
+    }
+}
+
+/// Initializes the embedded interpreter from the bundled, relocatable
+/// CPython distribution and confirms `import gigui` succeeds before any
+/// Tauri command runs. Must be called once, early in `run()`.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let layout = BundledLayout::resolve(app)?;
+
+    debug!("Bundled PYTHONHOME: {}", layout.python_home.display());
+    std::env::set_var("PYTHONHOME", &layout.python_home);
+
+    Python::with_gil(|py| -> PyResult<()> {
+        let sys = py.import_bound("sys")?;
+        let path = sys.getattr("path")?;
+
+        for dir in [&layout.project_python, &layout.site_packages, &layout.stdlib] {
+            insert_path(&path, dir)?;
+        }
+
+        debug!("Verifying embedded interpreter can import gigui");
+        py.import_bound("gigui")?;
+
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to initialize bundled Python runtime: {}", e))?;
+
+    info!("Bundled Python runtime initialized from {}", layout.python_home.display());
+    Ok(())
+}
+
+fn insert_path(sys_path: &Bound<'_, PyAny>, dir: &Path) -> PyResult<()> {
+    sys_path.call_method1("insert", (0, dir.to_string_lossy().as_ref()))?;
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Runs one repository analysis per isolated CPython sub-interpreter so
+//! `multicore`/`max_core_workers` can actually parallelize `execute_analysis`
+//! across `input_fstrs` instead of serializing everything through a single
+//! `Python::with_gil`.
+//!
+//! Sub-interpreter support is opt-in and unsafe: any `Py<T>` stored in a Rust
+//! `static` (interned strings, cached types, ...) is shared mutable state
+//! across interpreters and is undefined behavior if touched from more than
+//! one of them. This module therefore keeps every Python object it creates
+//! local to the worker thread that owns that interpreter, never leaks a
+//! `Py<T>` across the thread boundary, and only crosses threads with plain
+//! `String` JSON payloads. Built only when the `sub_interpreters` feature is
+//! enabled; callers fall back to the single-interpreter path otherwise.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+
+use log::debug;
+use pyo3::ffi;
+use pyo3::prelude::*;
+use pyo3::types::PyCFunction;
+
+use crate::commands::{cancel_requested, ProgressEvent, ProgressSink, Settings};
+
+/// One repository's analysis, run to completion on its own sub-interpreter
+/// thread. Returns the JSON-serialized `RepositoryResult` produced while
+/// that interpreter still held its own GIL, so the Rust side only ever
+/// touches plain strings across the thread boundary.
+///
+/// `ffi::Py_NewInterpreter()` (the legacy call) creates sub-interpreters that
+/// still share the single process-wide GIL, so a pool built on it would just
+/// serialize on that one GIL and gain nothing from the extra threads. To get
+/// real parallelism we need `Py_NewInterpreterFromConfig` with
+/// `gil = PyInterpreterConfig_OWN_GIL` (CPython >= 3.12), which gives each
+/// sub-interpreter its own independent GIL.
+fn analyze_one_repo(
+    python_dir: &str,
+    src_python_dir: &str,
+    repo_fstr: &str,
+    settings_json: &str,
+    on_progress: &Arc<dyn ProgressSink>,
+) -> Result<String, String> {
+    let mut config: ffi::PyInterpreterConfig = unsafe { std::mem::zeroed() };
+    config.use_main_obmalloc = 0;
+    config.allow_fork = 0;
+    config.allow_exec = 0;
+    config.allow_threads = 1;
+    config.allow_daemon_threads = 0;
+    config.check_multi_interp_extensions = 1;
+    config.gil = ffi::PyInterpreterConfig_OWN_GIL;
+
+    // SAFETY: this thread was just spawned by `std::thread::spawn` and holds
+    // no Python thread state / GIL of any kind, which is the precondition
+    // for creating an own-GIL sub-interpreter (unlike legacy
+    // `Py_NewInterpreter`, which instead requires the calling thread to
+    // already hold the main interpreter's GIL). On success, the new
+    // interpreter's thread state is made current on this thread and this
+    // thread now holds that interpreter's own, independent GIL.
+    let mut tstate: *mut ffi::PyThreadState = std::ptr::null_mut();
+    let status = unsafe { ffi::Py_NewInterpreterFromConfig(&mut tstate, &config) };
+    if unsafe { ffi::PyStatus_Exception(status) } != 0 || tstate.is_null() {
+        return Err(
+            "Py_NewInterpreterFromConfig failed; own-GIL sub-interpreters require CPython >= 3.12 built with support for multiple interpreters".to_string(),
+        );
+    }
+
+    let result = (|| -> PyResult<String> {
+        // SAFETY: `tstate` was just made current on this thread by
+        // `Py_NewInterpreterFromConfig`, and this thread holds its own GIL
+        // (not `PyGILState_Ensure`-tracked, which isn't sub-interpreter
+        // aware), so it's sound to assume the GIL is already acquired here.
+        let py = unsafe { Python::assume_gil_acquired() };
+
+        let sys = py.import_bound("sys")?;
+        let path = sys.getattr("path")?;
+        path.call_method1("insert", (0, python_dir))?;
+        path.call_method1("insert", (0, src_python_dir))?;
+
+        debug!("[sub-interpreter] importing gigui for repo {}", repo_fstr);
+        py.import_bound("gigui")?;
+
+        let main_module = py.import_bound("main")?;
+
+        // Each sub-interpreter gets its own `PyCFunction`s: the closures
+        // they wrap live in this thread's Rust stack and only ever run on
+        // this thread's GIL, so no `Py<T>` crosses interpreters.
+        let sink = Arc::clone(on_progress);
+        let progress_callback = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |call_args, _kwargs| -> PyResult<()> {
+                let repo: String = call_args.get_item(0)?.extract()?;
+                let phase: String = call_args.get_item(1)?.extract()?;
+                let files_processed: i32 = call_args.get_item(2)?.extract()?;
+                let percent: f64 = call_args.get_item(3)?.extract()?;
+                sink.send(ProgressEvent::Progress {
+                    repo,
+                    phase,
+                    files_processed,
+                    percent,
+                });
+                Ok(())
+            },
+        )?;
+
+        let is_cancelled = PyCFunction::new_closure_bound(py, None, None, |_args, _kwargs| -> PyResult<bool> {
+            Ok(cancel_requested().load(Ordering::SeqCst))
+        })?;
+
+        let result = main_module.call_method1(
+            "execute_analysis_single_repo",
+            (settings_json, repo_fstr, progress_callback, is_cancelled),
+        )?;
+        result.extract::<String>()
+    })();
+
+    // SAFETY: `tstate` is still current on this thread; this tears down the
+    // sub-interpreter created above and releases its GIL. Must run on the
+    // same thread that created it, with no `Py<T>` from it still alive
+    // anywhere else.
+    unsafe {
+        ffi::Py_EndInterpreter(tstate);
+    }
+
+    result.map_err(|e| format!("Python call failed: {}", e))
+}
+
+/// Runs `execute_analysis_single_repo` for each of `repo_fstrs` on its own
+/// sub-interpreter thread, bounded to `max_core_workers` concurrent workers,
+/// forwarding `on_progress` into each sub-interpreter and honoring
+/// `cancel_requested` between chunks. Returns each completed repo's
+/// JSON-serialized `RepositoryResult` (in order) plus whether the run was
+/// aborted before every repo finished.
+pub fn analyze_repos_in_parallel(
+    settings: &Settings,
+    repo_fstrs: &[String],
+    on_progress: Arc<dyn ProgressSink>,
+) -> Result<(Vec<String>, bool), String> {
+    let python_dir = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?
+        .join("python")
+        .to_string_lossy()
+        .into_owned();
+    let src_python_dir = std::env::current_dir()
+        .map_err(|e| format!("Failed to get current directory: {}", e))?
+        .join("src-tauri")
+        .join("src-python")
+        .to_string_lossy()
+        .into_owned();
+
+    let settings_json = serde_json::to_string(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    let pool_size = (settings.max_core_workers.max(1) as usize).min(repo_fstrs.len().max(1));
+    debug!("Sub-interpreter pool size: {} for {} repos", pool_size, repo_fstrs.len());
+
+    let mut results = Vec::with_capacity(repo_fstrs.len());
+    let mut aborted = false;
+    for chunk in repo_fstrs.chunks(pool_size.max(1)) {
+        if cancel_requested().load(Ordering::SeqCst) {
+            debug!("Cancellation requested; not starting remaining sub-interpreter chunks");
+            aborted = true;
+            break;
+        }
+
+        let mut handles = Vec::with_capacity(chunk.len());
+        for repo_fstr in chunk {
+            let python_dir = python_dir.clone();
+            let src_python_dir = src_python_dir.clone();
+            let repo_fstr = repo_fstr.clone();
+            let settings_json = settings_json.clone();
+            let sink = Arc::clone(&on_progress);
+            handles.push(thread::spawn(move || {
+                analyze_one_repo(&python_dir, &src_python_dir, &repo_fstr, &settings_json, &sink)
+            }));
+        }
+        for handle in handles {
+            match handle.join() {
+                Ok(result) => results.push(result?),
+                Err(_) => return Err("Sub-interpreter worker thread panicked".to_string()),
+            }
+        }
+    }
+
+    Ok((results, aborted))
+}
+
+/// Whether the sub-interpreter pool should be used for this request: the
+/// feature must be compiled in, `multicore` must be enabled, and there must
+/// be more than one repo to actually gain anything from parallelizing.
+pub fn should_use_pool(settings: &Settings, repo_fstrs: &[String]) -> bool {
+    if !settings.multicore {
+        return false;
+    }
+    if repo_fstrs.len() <= 1 {
+        debug!("Only one repo requested; skipping sub-interpreter pool");
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repos(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("repo{i}")).collect()
+    }
+
+    #[test]
+    fn should_use_pool_requires_multicore() {
+        let mut settings = Settings::default();
+        settings.multicore = false;
+        assert!(!should_use_pool(&settings, &repos(2)));
+    }
+
+    #[test]
+    fn should_use_pool_requires_more_than_one_repo() {
+        let mut settings = Settings::default();
+        settings.multicore = true;
+        assert!(!should_use_pool(&settings, &repos(1)));
+        assert!(!should_use_pool(&settings, &repos(0)));
+    }
+
+    #[test]
+    fn should_use_pool_true_when_both_conditions_met() {
+        let mut settings = Settings::default();
+        settings.multicore = true;
+        assert!(should_use_pool(&settings, &repos(2)));
+    }
+}